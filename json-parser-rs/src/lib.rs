@@ -0,0 +1,671 @@
+use indexmap::IndexMap;
+
+mod path;
+
+pub use path::{select, PathError};
+
+/// A parsed JSON value.
+pub enum JsonValue {
+    Null,
+    Boolean(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(IndexMap<String, JsonValue>),
+}
+
+/// A JSON number, keeping the integer/float distinction the input had
+/// instead of collapsing both into a lossy `f32`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl ToString for JsonValue {
+    fn to_string(&self) -> String {
+        match self {
+            JsonValue::Null => "null".to_string(),
+            JsonValue::Boolean(b) => b.to_string(),
+            JsonValue::Number(Number::Integer(n)) => n.to_string(),
+            JsonValue::Number(Number::Float(n)) => {
+                let s = n.to_string();
+                if s.contains('.') || s.contains('e') || s.contains('E') {
+                    s
+                } else {
+                    format!("{}.0", s)
+                }
+            }
+            JsonValue::String(s) => format!("\"{}\"", escape_json_string(s)),
+            JsonValue::Array(a) => {
+                if a.is_empty() {
+                    return "[]".to_string();
+                }
+                let mut s = "[".to_string();
+                for value in a.iter() {
+                    s.push_str(&format!("{}, ", value.to_string()));
+                }
+                s.pop(); // Pop final comma
+                s.pop(); // Pop final space
+                s.push(']');
+                s
+            }
+            JsonValue::Object(o) => {
+                if o.is_empty() {
+                    return "{}".to_string();
+                }
+                let mut s = "{".to_string();
+                for (key, value) in o.iter() {
+                    s.push_str(&format!(
+                        "\"{}\": {}, ",
+                        escape_json_string(key),
+                        value.to_string()
+                    ));
+                }
+                s.pop(); // Pop final comma
+                s.pop(); // Pop final space
+                s.push('}');
+                s
+            }
+        }
+    }
+}
+
+impl JsonValue {
+    /// Renders `self` as JSON with `indent` spaces per nesting level, one
+    /// element per line. Strings are escaped the same way as in the compact
+    /// `to_string`, so the output parses back to the same value.
+    pub fn to_pretty_string(&self, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, indent, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        match self {
+            JsonValue::Array(items) => {
+                if items.is_empty() {
+                    out.push_str("[]");
+                    return;
+                }
+                out.push_str("[\n");
+                for (i, value) in items.iter().enumerate() {
+                    out.push_str(&" ".repeat(indent * (depth + 1)));
+                    value.write_pretty(out, indent, depth + 1);
+                    if i + 1 < items.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                out.push_str(&" ".repeat(indent * depth));
+                out.push(']');
+            }
+            JsonValue::Object(entries) => {
+                if entries.is_empty() {
+                    out.push_str("{}");
+                    return;
+                }
+                out.push_str("{\n");
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(&" ".repeat(indent * (depth + 1)));
+                    out.push_str(&format!("\"{}\": ", escape_json_string(key)));
+                    value.write_pretty(out, indent, depth + 1);
+                    if i + 1 < entries.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                out.push_str(&" ".repeat(indent * depth));
+                out.push('}');
+            }
+            other => out.push_str(&other.to_string()),
+        }
+    }
+
+    /// Returns the inner map if this is an object.
+    pub fn as_object(&self) -> Option<&IndexMap<String, JsonValue>> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner elements if this is an array.
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner string slice if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns this number as an `f64`, widening integers as needed.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(Number::Integer(n)) => Some(*n as f64),
+            JsonValue::Number(Number::Float(n)) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner bool if this is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this value is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// Looks up `key` if this is an object.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.as_object()?.get(key)
+    }
+
+    /// Looks up `index` if this is an array.
+    pub fn get_index(&self, index: usize) -> Option<&JsonValue> {
+        self.as_array()?.get(index)
+    }
+}
+
+/// Escapes a string's contents the same way `Parser::parse_string` decodes
+/// them, so serialized output round-trips back through the parser.
+fn escape_json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '/' => escaped.push_str("\\/"),
+            '\u{0008}' => escaped.push_str("\\b"),
+            '\u{000C}' => escaped.push_str("\\f"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    UnexpectedToken(usize),
+    UnexpectedEndOfInput,
+    TrailingComma(usize),
+    MaxDepthExceeded(usize),
+    LeadingZero(usize),
+    NumberOutOfRange(usize),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Vec<(usize, char)>,
+    index: usize,
+}
+
+const MAX_DEPTH: u32 = 20;
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            input,
+            chars: input.char_indices().collect(),
+            index: 0,
+        }
+    }
+
+    fn parse(&mut self) -> Result<JsonValue, ParseError> {
+        self.skip_whitespace();
+        let res = self.parse_value(0);
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some(_) => Err(ParseError::UnexpectedToken(self.position())),
+            None => res,
+        }
+    }
+
+    fn parse_value(&mut self, depth: u32) -> Result<JsonValue, ParseError> {
+        if depth >= MAX_DEPTH {
+            return Err(ParseError::MaxDepthExceeded(self.position()));
+        }
+
+        self.skip_whitespace();
+        let c = self.peek().ok_or(ParseError::UnexpectedEndOfInput)?;
+        // Match object, string, boolean, null and number
+        match c {
+            '{' => self.parse_object(depth),
+            '[' => self.parse_array(depth),
+            '"' => self.parse_string(),
+            't' | 'f' => self.parse_boolean(),
+            'n' => self.parse_null(),
+            c if c.is_digit(10) || c == '-' => self.parse_number(),
+            _ => Err(ParseError::UnexpectedToken(self.position())),
+        }
+    }
+
+    fn parse_object(&mut self, depth: u32) -> Result<JsonValue, ParseError> {
+        if depth >= MAX_DEPTH {
+            return Err(ParseError::MaxDepthExceeded(self.position()));
+        }
+
+        let mut object: IndexMap<String, JsonValue> = IndexMap::new();
+        self.consume(); // consume '{'
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.consume();
+                break;
+            }
+
+            if let JsonValue::String(key) = self.parse_string()? {
+                self.skip_whitespace();
+
+                if self.peek() != Some(':') {
+                    match self.peek() {
+                        Some(_) => return Err(ParseError::UnexpectedToken(self.position())),
+                        None => return Err(ParseError::UnexpectedEndOfInput),
+                    }
+                }
+                self.consume(); // consume ':'
+
+                let value = self.parse_value(depth + 1)?;
+                object.insert(key, value);
+
+                self.skip_whitespace();
+                match self.consume() {
+                    Some('}') => break,
+                    Some(',') => {
+                        self.skip_whitespace();
+                        if self.peek() == Some('}') {
+                            return Err(ParseError::TrailingComma(self.position()));
+                        }
+                        continue;
+                    }
+                    _ => return Err(ParseError::UnexpectedEndOfInput),
+                }
+            }
+        }
+
+        Ok(JsonValue::Object(object))
+    }
+
+    fn parse_array(&mut self, depth: u32) -> Result<JsonValue, ParseError> {
+        if depth >= MAX_DEPTH {
+            return Err(ParseError::MaxDepthExceeded(self.position()));
+        }
+
+        let mut array: Vec<JsonValue> = Vec::new();
+        self.consume(); // consume '['
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.consume();
+                break;
+            }
+
+            let value = self.parse_value(depth + 1)?;
+            array.push(value);
+
+            self.skip_whitespace();
+            match self.consume() {
+                Some(']') => break,
+                Some(',') => {
+                    self.skip_whitespace();
+                    if self.peek() == Some(']') {
+                        return Err(ParseError::TrailingComma(self.position()));
+                    }
+                    continue;
+                }
+                _ => return Err(ParseError::UnexpectedEndOfInput),
+            }
+        }
+
+        Ok(JsonValue::Array(array))
+    }
+
+    fn parse_string(&mut self) -> Result<JsonValue, ParseError> {
+        let mut s = String::new();
+        self.consume(); // consume '"'
+
+        loop {
+            match self.consume() {
+                Some('"') => break,
+
+                // Error if tab, newline, or carriage return is not escaped
+                Some('\t') | Some('\n') | Some('\r') => {
+                    return Err(ParseError::UnexpectedToken(self.position()))
+                }
+
+                // Handle escape characters
+                Some('\\') => match self.consume() {
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some('/') => s.push('/'),
+                    Some('b') => s.push('\u{0008}'),
+                    Some('f') => s.push('\u{000C}'),
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    Some('u') => {
+                        let mut hex = String::new();
+                        for _ in 0..4 {
+                            match self.consume() {
+                                Some(c) if c.is_digit(16) => hex.push(c),
+                                Some(_) => return Err(ParseError::UnexpectedToken(self.position())),
+                                None => return Err(ParseError::UnexpectedEndOfInput),
+                            }
+                        }
+                        let codepoint = u32::from_str_radix(&hex, 16).unwrap();
+                        match std::char::from_u32(codepoint) {
+                            Some(c) => s.push(c),
+                            None => return Err(ParseError::UnexpectedToken(self.position())),
+                        }
+                    }
+                    Some(_) => return Err(ParseError::UnexpectedToken(self.position())),
+                    None => return Err(ParseError::UnexpectedEndOfInput),
+                },
+
+                Some(c) => s.push(c),
+                None => return Err(ParseError::UnexpectedEndOfInput),
+            }
+        }
+
+        Ok(JsonValue::String(s))
+    }
+
+    fn parse_boolean(&mut self) -> Result<JsonValue, ParseError> {
+        let mut s = String::new();
+
+        loop {
+            match self.peek() {
+                Some(c) if c.is_alphabetic() => {
+                    s.push(c);
+                    self.consume();
+                }
+                _ => break,
+            }
+        }
+
+        match s.as_str() {
+            "true" => Ok(JsonValue::Boolean(true)),
+            "false" => Ok(JsonValue::Boolean(false)),
+            _ => Err(ParseError::UnexpectedToken(self.position())),
+        }
+    }
+
+    fn parse_null(&mut self) -> Result<JsonValue, ParseError> {
+        let mut s = String::new();
+
+        loop {
+            match self.peek() {
+                Some(c) if c.is_alphabetic() => {
+                    s.push(c);
+                    self.consume();
+                }
+                _ => break,
+            }
+        }
+
+        if s == "null" {
+            Ok(JsonValue::Null)
+        } else {
+            Err(ParseError::UnexpectedToken(self.position()))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, ParseError> {
+        let mut s = String::new();
+        let mut is_negative = false;
+        let mut is_float = false;
+        let mut seen_dot = false;
+        let mut seen_exponent = false;
+
+        if self.peek() == Some('-') {
+            s.push('-');
+            self.consume();
+            is_negative = true;
+        }
+
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() => {
+                    s.push(c);
+                    self.consume();
+                }
+                Some('.') if !seen_dot && !seen_exponent => {
+                    s.push('.');
+                    self.consume();
+                    is_float = true;
+                    seen_dot = true;
+
+                    match self.peek() {
+                        Some(c) if c.is_ascii_digit() => (),
+                        Some(_) => return Err(ParseError::UnexpectedToken(self.position())),
+                        None => return Err(ParseError::UnexpectedEndOfInput),
+                    }
+                }
+                Some(c @ ('e' | 'E')) if !seen_exponent => {
+                    s.push(c);
+                    self.consume();
+                    is_float = true;
+                    seen_exponent = true;
+
+                    if let Some(sign @ ('+' | '-')) = self.peek() {
+                        s.push(sign);
+                        self.consume();
+                    }
+
+                    match self.peek() {
+                        Some(c) if c.is_ascii_digit() => (),
+                        Some(_) => return Err(ParseError::UnexpectedToken(self.position())),
+                        None => return Err(ParseError::UnexpectedEndOfInput),
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        if is_negative && s.len() == 1 {
+            return Err(ParseError::UnexpectedToken(self.position()));
+        }
+
+        if !is_float && s.len() > 1 && (s.starts_with('0') || s.starts_with("-0")) {
+            return Err(ParseError::LeadingZero(self.position()));
+        }
+
+        if is_float {
+            let value: f64 = s.parse().unwrap();
+            if !value.is_finite() {
+                return Err(ParseError::NumberOutOfRange(self.position()));
+            }
+            return Ok(JsonValue::Number(Number::Float(value)));
+        }
+
+        match s.parse::<i64>() {
+            Ok(value) => Ok(JsonValue::Number(Number::Integer(value))),
+            Err(_) => {
+                let value: f64 = s.parse().unwrap();
+                Ok(JsonValue::Number(Number::Float(value)))
+            }
+        }
+    }
+
+    /// Current read position as a byte offset into `input`, used for error messages.
+    fn position(&self) -> usize {
+        self.chars
+            .get(self.index)
+            .map(|&(byte_offset, _)| byte_offset)
+            .unwrap_or(self.input.len())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).map(|&(_, c)| c)
+    }
+
+    fn consume(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.consume();
+        }
+    }
+}
+
+/// Parses a complete JSON document from `input`, accepting any JSON value
+/// (object, array, string, number, bool, or null) at the root.
+pub fn parse(input: &str) -> Result<JsonValue, ParseError> {
+    Parser::new(input).parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_large_input_in_linear_time() {
+        // A flat array of a few million numbers is several MB of input. With the
+        // old `chars().nth(position)` cursor this would take minutes; linear
+        // parsing should finish in well under a second.
+        let elements = 2_000_000;
+        let mut json = String::with_capacity(elements * 2);
+        json.push('[');
+        for i in 0..elements {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str(&i.to_string());
+        }
+        json.push(']');
+
+        let start = std::time::Instant::now();
+        let result = parse(&json);
+        let elapsed = start.elapsed();
+
+        assert!(result.is_ok(), "failed to parse large array");
+        assert!(
+            elapsed.as_secs() < 5,
+            "parsing took too long, cursor may have regressed to quadratic: {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn escapes_special_characters_in_strings() {
+        let value = JsonValue::String("quote\" back\\slash /slash\ttab\nline".to_string());
+        assert_eq!(
+            value.to_string(),
+            "\"quote\\\" back\\\\slash \\/slash\\ttab\\nline\""
+        );
+    }
+
+    #[test]
+    fn escapes_control_codepoints_as_unicode_escapes() {
+        let value = JsonValue::String("\u{0001}".to_string());
+        assert_eq!(value.to_string(), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn pretty_prints_nested_values_with_indentation() {
+        let json = r#"{"a": [1, 2], "b": {}}"#;
+        let value = parse(json).unwrap();
+        assert_eq!(
+            value.to_pretty_string(2),
+            "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}"
+        );
+    }
+
+    #[test]
+    fn pretty_prints_empty_containers_compactly() {
+        assert_eq!(JsonValue::Array(vec![]).to_pretty_string(2), "[]");
+        assert_eq!(JsonValue::Object(IndexMap::new()).to_pretty_string(2), "{}");
+    }
+
+    #[test]
+    fn parses_integers_as_integer_numbers() {
+        match parse("42").unwrap() {
+            JsonValue::Number(Number::Integer(n)) => assert_eq!(n, 42),
+            other => panic!("expected an integer, got {}", other.to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_decimals_without_losing_precision() {
+        match parse("0.1").unwrap() {
+            JsonValue::Number(Number::Float(n)) => assert_eq!(n, 0.1),
+            other => panic!("expected a float, got {}", other.to_string()),
+        }
+    }
+
+    #[test]
+    fn errors_on_a_dot_with_no_following_digit() {
+        assert!(parse("5.").is_err());
+        assert!(parse("-3.").is_err());
+        assert!(parse("[12.,1]").is_err());
+    }
+
+    #[test]
+    fn errors_on_an_exponent_that_overflows_f64() {
+        assert!(parse("1e400").is_err());
+    }
+
+    #[test]
+    fn falls_back_to_float_on_integer_overflow() {
+        let huge = "99999999999999999999";
+        match parse(huge).unwrap() {
+            JsonValue::Number(Number::Float(n)) => assert_eq!(n, huge.parse::<f64>().unwrap()),
+            other => panic!("expected a float fallback, got {}", other.to_string()),
+        }
+    }
+
+    #[test]
+    fn round_trips_integers_and_floats_through_to_string() {
+        assert_eq!(parse("42").unwrap().to_string(), "42");
+        assert_eq!(parse("1.50").unwrap().to_string(), "1.5");
+        assert_eq!(parse("1e3").unwrap().to_string(), "1000.0");
+    }
+
+    #[test]
+    fn accepts_scalar_values_at_the_root() {
+        assert!(parse("\"hello\"").unwrap().as_str() == Some("hello"));
+        assert!(parse("true").unwrap().as_bool() == Some(true));
+        assert!(parse("null").unwrap().is_null());
+    }
+
+    #[test]
+    fn typed_accessors_navigate_objects_and_arrays() {
+        let value = parse(r#"{"name": "book", "tags": ["a", "b"], "price": 9.5}"#).unwrap();
+        assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("book"));
+        assert_eq!(
+            value
+                .get("tags")
+                .and_then(|tags| tags.get_index(1))
+                .and_then(JsonValue::as_str),
+            Some("b")
+        );
+        assert_eq!(value.get("price").and_then(JsonValue::as_f64), Some(9.5));
+        assert!(value.get("missing").is_none());
+    }
+}
@@ -0,0 +1,629 @@
+use crate::{JsonValue, Number};
+
+/// Errors produced while parsing or evaluating a JSONPath expression.
+#[derive(Debug)]
+pub enum PathError {
+    UnexpectedToken(usize),
+    UnexpectedEndOfInput,
+    MissingRoot,
+    InvalidIndex(String),
+    InvalidLiteral(String),
+    UnknownOperator(String),
+    NotAnArray(String),
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::UnexpectedToken(pos) => {
+                write!(f, "unexpected token at position {} in path", pos)
+            }
+            PathError::UnexpectedEndOfInput => write!(f, "unexpected end of path"),
+            PathError::MissingRoot => write!(f, "path must start with '$'"),
+            PathError::InvalidIndex(text) => write!(f, "invalid index '{}'", text),
+            PathError::InvalidLiteral(text) => write!(f, "invalid literal '{}'", text),
+            PathError::UnknownOperator(op) => write!(f, "unknown filter operator '{}'", op),
+            PathError::NotAnArray(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Literal {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone)]
+struct Filter {
+    key: String,
+    op: CompareOp,
+    literal: Literal,
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Child(String),
+    Index(i64),
+    Wildcard,
+    RecursiveDescent(String),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Filter(Filter),
+}
+
+/// Parses `path` and evaluates it against `root`, returning references to every
+/// matching node in document order.
+pub fn select<'a>(
+    root: &'a JsonValue,
+    path: &str,
+) -> Result<Vec<&'a JsonValue>, PathError> {
+    let selectors = PathParser::new(path).parse()?;
+
+    let mut current: Vec<&'a JsonValue> = vec![root];
+    for selector in &selectors {
+        current = apply_selector(current, selector)?;
+    }
+    Ok(current)
+}
+
+fn apply_selector<'a>(
+    nodes: Vec<&'a JsonValue>,
+    selector: &Selector,
+) -> Result<Vec<&'a JsonValue>, PathError> {
+    match selector {
+        Selector::Child(name) => Ok(nodes
+            .into_iter()
+            .filter_map(|node| child(node, name))
+            .collect()),
+
+        Selector::Index(index) => {
+            let mut out = Vec::new();
+            for node in nodes {
+                match node {
+                    JsonValue::Array(items) => {
+                        if let Some(i) = array_index(items, *index) {
+                            out.push(&items[i]);
+                        }
+                    }
+                    other => {
+                        return Err(PathError::NotAnArray(format!(
+                            "cannot index {} with [{}]",
+                            type_name(other),
+                            index
+                        )))
+                    }
+                }
+            }
+            Ok(out)
+        }
+
+        Selector::Wildcard => Ok(nodes.into_iter().flat_map(children).collect()),
+
+        Selector::RecursiveDescent(name) => {
+            let mut matches = Vec::new();
+            for node in nodes {
+                recursive_descend(node, name, &mut matches);
+            }
+            Ok(matches)
+        }
+
+        Selector::Slice(start, end, step) => {
+            let mut out = Vec::new();
+            for node in nodes {
+                match node {
+                    JsonValue::Array(items) => {
+                        out.extend(
+                            slice_indices(items.len(), *start, *end, *step)?.map(|i| &items[i]),
+                        );
+                    }
+                    other => {
+                        return Err(PathError::NotAnArray(format!(
+                            "cannot slice {}",
+                            type_name(other)
+                        )))
+                    }
+                }
+            }
+            Ok(out)
+        }
+
+        Selector::Filter(filter) => {
+            let mut out = Vec::new();
+            for node in nodes {
+                out.extend(children(node).into_iter().filter(|child| {
+                    matches_filter(child, filter)
+                }));
+            }
+            Ok(out)
+        }
+    }
+}
+
+fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Object(_) => "an object",
+        JsonValue::Array(_) => "an array",
+        JsonValue::String(_) => "a string",
+        JsonValue::Number(_) => "a number",
+        JsonValue::Boolean(_) => "a boolean",
+        JsonValue::Null => "null",
+    }
+}
+
+fn child<'a>(node: &'a JsonValue, name: &str) -> Option<&'a JsonValue> {
+    match node {
+        JsonValue::Object(map) => map.get(name),
+        _ => None,
+    }
+}
+
+fn children(node: &JsonValue) -> Vec<&JsonValue> {
+    match node {
+        JsonValue::Object(map) => map.values().collect(),
+        JsonValue::Array(items) => items.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn array_index(items: &[JsonValue], index: i64) -> Option<usize> {
+    let resolved = if index < 0 {
+        items.len() as i64 + index
+    } else {
+        index
+    };
+    if resolved < 0 || resolved as usize >= items.len() {
+        None
+    } else {
+        Some(resolved as usize)
+    }
+}
+
+fn recursive_descend<'a>(node: &'a JsonValue, name: &str, out: &mut Vec<&'a JsonValue>) {
+    match node {
+        JsonValue::Object(map) => {
+            for (key, value) in map {
+                if key == name {
+                    out.push(value);
+                }
+                recursive_descend(value, name, out);
+            }
+        }
+        JsonValue::Array(items) => {
+            for value in items {
+                recursive_descend(value, name, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn slice_indices(
+    len: usize,
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+) -> Result<Box<dyn Iterator<Item = usize>>, PathError> {
+    let step = step.unwrap_or(1);
+    if step == 0 {
+        return Err(PathError::InvalidIndex("slice step cannot be 0".to_string()));
+    }
+
+    let clamp = |value: i64| -> usize {
+        let resolved = if value < 0 { len as i64 + value } else { value };
+        resolved.clamp(0, len as i64) as usize
+    };
+
+    if step > 0 {
+        let start = start.map(clamp).unwrap_or(0);
+        let end = end.map(clamp).unwrap_or(len);
+        Ok(Box::new((start..end).step_by(step as usize)))
+    } else {
+        let start = start.map(clamp).unwrap_or(len.saturating_sub(1)) as i64;
+        // `end` has no clamped default: clamping `None` to `0` would be
+        // indistinguishable from an explicit `[:0:-1]` stopping just before
+        // index 0, dropping the first element. Use `-1` as the sentinel for
+        // "no lower bound" so the loop below includes index 0.
+        let end = end.map(clamp).map(|e| e as i64).unwrap_or(-1);
+        let mut indices = Vec::new();
+        let mut i = start;
+        while i > end {
+            indices.push(i as usize);
+            i += step;
+        }
+        Ok(Box::new(indices.into_iter()))
+    }
+}
+
+fn matches_filter(node: &JsonValue, filter: &Filter) -> bool {
+    let value = match child(node, &filter.key) {
+        Some(value) => value,
+        None => return false,
+    };
+
+    match (&filter.literal, value) {
+        (Literal::Null, JsonValue::Null) => compare_eq_ne(filter.op, true),
+        (Literal::Bool(expected), JsonValue::Boolean(actual)) => {
+            compare_eq_ne(filter.op, *expected == *actual)
+        }
+        (Literal::String(expected), JsonValue::String(actual)) => {
+            compare_ord(filter.op, actual.as_str().cmp(expected.as_str()))
+        }
+        (Literal::Number(expected), JsonValue::Number(actual)) => {
+            let actual = match actual {
+                Number::Integer(n) => *n as f64,
+                Number::Float(n) => *n,
+            };
+            actual
+                .partial_cmp(expected)
+                .map(|ordering| compare_ord(filter.op, ordering))
+                .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+fn compare_eq_ne(op: CompareOp, equal: bool) -> bool {
+    match op {
+        CompareOp::Eq => equal,
+        CompareOp::Ne => !equal,
+        _ => false,
+    }
+}
+
+fn compare_ord(op: CompareOp, ordering: std::cmp::Ordering) -> bool {
+    use std::cmp::Ordering::*;
+    matches!(
+        (op, ordering),
+        (CompareOp::Eq, Equal)
+            | (CompareOp::Ne, Less)
+            | (CompareOp::Ne, Greater)
+            | (CompareOp::Lt, Less)
+            | (CompareOp::Le, Less)
+            | (CompareOp::Le, Equal)
+            | (CompareOp::Gt, Greater)
+            | (CompareOp::Ge, Greater)
+            | (CompareOp::Ge, Equal)
+    )
+}
+
+/// Tokenizes and parses a JSONPath expression into a sequence of [`Selector`]s.
+struct PathParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl PathParser {
+    fn new(input: &str) -> Self {
+        PathParser {
+            chars: input.chars().collect(),
+            position: 0,
+        }
+    }
+
+    fn parse(&mut self) -> Result<Vec<Selector>, PathError> {
+        if self.consume() != Some('$') {
+            return Err(PathError::MissingRoot);
+        }
+
+        let mut selectors = Vec::new();
+        while self.peek().is_some() {
+            match self.peek() {
+                Some('.') => {
+                    self.consume();
+                    if self.peek() == Some('.') {
+                        self.consume();
+                        selectors.push(Selector::RecursiveDescent(self.read_name()?));
+                    } else if self.peek() == Some('*') {
+                        self.consume();
+                        selectors.push(Selector::Wildcard);
+                    } else {
+                        selectors.push(Selector::Child(self.read_name()?));
+                    }
+                }
+                Some('[') => selectors.push(self.parse_bracket()?),
+                _ => return Err(PathError::UnexpectedToken(self.position)),
+            }
+        }
+
+        Ok(selectors)
+    }
+
+    fn parse_bracket(&mut self) -> Result<Selector, PathError> {
+        self.consume(); // '['
+
+        let selector = match self.peek() {
+            Some('*') => {
+                self.consume();
+                Selector::Wildcard
+            }
+            Some('?') => self.parse_filter()?,
+            Some('"') | Some('\'') => Selector::Child(self.read_quoted_name()?),
+            _ => self.parse_index_or_slice()?,
+        };
+
+        self.skip_whitespace();
+        if self.consume() != Some(']') {
+            return Err(PathError::UnexpectedToken(self.position));
+        }
+
+        Ok(selector)
+    }
+
+    fn parse_index_or_slice(&mut self) -> Result<Selector, PathError> {
+        let first = self.read_optional_int()?;
+
+        if self.peek() == Some(':') {
+            self.consume();
+            let second = self.read_optional_int()?;
+            let step = if self.peek() == Some(':') {
+                self.consume();
+                self.read_optional_int()?
+            } else {
+                None
+            };
+            Ok(Selector::Slice(first, second, step))
+        } else {
+            match first {
+                Some(index) => Ok(Selector::Index(index)),
+                None => Err(PathError::UnexpectedToken(self.position)),
+            }
+        }
+    }
+
+    fn parse_filter(&mut self) -> Result<Selector, PathError> {
+        self.consume(); // '?'
+        if self.consume() != Some('(') {
+            return Err(PathError::UnexpectedToken(self.position));
+        }
+        if self.consume() != Some('@') {
+            return Err(PathError::UnexpectedToken(self.position));
+        }
+        if self.consume() != Some('.') {
+            return Err(PathError::UnexpectedToken(self.position));
+        }
+        let key = self.read_name()?;
+
+        self.skip_whitespace();
+        let op = self.read_operator()?;
+        self.skip_whitespace();
+        let literal = self.read_literal()?;
+        self.skip_whitespace();
+
+        if self.consume() != Some(')') {
+            return Err(PathError::UnexpectedToken(self.position));
+        }
+
+        Ok(Selector::Filter(Filter { key, op, literal }))
+    }
+
+    fn read_operator(&mut self) -> Result<CompareOp, PathError> {
+        let start = self.position;
+        let mut op = String::new();
+        while matches!(self.peek(), Some('=') | Some('!') | Some('<') | Some('>')) {
+            op.push(self.consume().unwrap());
+        }
+        match op.as_str() {
+            "==" => Ok(CompareOp::Eq),
+            "!=" => Ok(CompareOp::Ne),
+            "<" => Ok(CompareOp::Lt),
+            "<=" => Ok(CompareOp::Le),
+            ">" => Ok(CompareOp::Gt),
+            ">=" => Ok(CompareOp::Ge),
+            _ => {
+                self.position = start;
+                Err(PathError::UnknownOperator(op))
+            }
+        }
+    }
+
+    fn read_literal(&mut self) -> Result<Literal, PathError> {
+        match self.peek() {
+            Some('"') | Some('\'') => Ok(Literal::String(self.read_quoted_name()?)),
+            Some('t') | Some('f') => {
+                let word = self.read_name()?;
+                match word.as_str() {
+                    "true" => Ok(Literal::Bool(true)),
+                    "false" => Ok(Literal::Bool(false)),
+                    other => Err(PathError::InvalidLiteral(other.to_string())),
+                }
+            }
+            Some('n') => {
+                let word = self.read_name()?;
+                if word == "null" {
+                    Ok(Literal::Null)
+                } else {
+                    Err(PathError::InvalidLiteral(word))
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' => {
+                let start = self.position;
+                self.consume();
+                while matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '.') {
+                    self.consume();
+                }
+                let text: String = self.chars[start..self.position].iter().collect();
+                text.parse::<f64>()
+                    .map(Literal::Number)
+                    .map_err(|_| PathError::InvalidLiteral(text))
+            }
+            _ => Err(PathError::UnexpectedToken(self.position)),
+        }
+    }
+
+    fn read_name(&mut self) -> Result<String, PathError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_' || c == '$') {
+            self.consume();
+        }
+        if self.position == start {
+            return Err(PathError::UnexpectedToken(self.position));
+        }
+        Ok(self.chars[start..self.position].iter().collect())
+    }
+
+    fn read_quoted_name(&mut self) -> Result<String, PathError> {
+        let quote = self.consume().ok_or(PathError::UnexpectedEndOfInput)?;
+        let start = self.position;
+        while self.peek() != Some(quote) {
+            if self.consume().is_none() {
+                return Err(PathError::UnexpectedEndOfInput);
+            }
+        }
+        let name = self.chars[start..self.position].iter().collect();
+        self.consume(); // closing quote
+        Ok(name)
+    }
+
+    fn read_optional_int(&mut self) -> Result<Option<i64>, PathError> {
+        self.skip_whitespace();
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.consume();
+        }
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.consume();
+        }
+        if self.position == start || (self.position == start + 1 && self.chars[start] == '-') {
+            return Ok(None);
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        text.parse::<i64>()
+            .map(Some)
+            .map_err(|_| PathError::InvalidIndex(text))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn consume(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.consume();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn store() -> JsonValue {
+        let mut book1 = IndexMap::new();
+        book1.insert("category".to_string(), JsonValue::String("fiction".to_string()));
+        book1.insert("price".to_string(), JsonValue::Number(Number::Float(8.99)));
+
+        let mut book2 = IndexMap::new();
+        book2.insert("category".to_string(), JsonValue::String("reference".to_string()));
+        book2.insert("price".to_string(), JsonValue::Number(Number::Float(22.99)));
+
+        let mut store = IndexMap::new();
+        store.insert(
+            "book".to_string(),
+            JsonValue::Array(vec![JsonValue::Object(book1), JsonValue::Object(book2)]),
+        );
+
+        let mut root = IndexMap::new();
+        root.insert("store".to_string(), JsonValue::Object(store));
+        JsonValue::Object(root)
+    }
+
+    fn numbers(values: &[&JsonValue]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|value| match value {
+                JsonValue::Number(Number::Float(n)) => *n,
+                JsonValue::Number(Number::Integer(n)) => *n as f64,
+                _ => panic!("expected a number"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn selects_all_prices_with_wildcard() {
+        let root = store();
+        let matches = select(&root, "$.store.book[*].price").unwrap();
+        assert_eq!(numbers(&matches), vec![8.99, 22.99]);
+    }
+
+    #[test]
+    fn selects_with_recursive_descent() {
+        let root = store();
+        let matches = select(&root, "$..price").unwrap();
+        assert_eq!(numbers(&matches), vec![8.99, 22.99]);
+    }
+
+    #[test]
+    fn selects_with_negative_index() {
+        let root = store();
+        let matches = select(&root, "$.store.book[-1].price").unwrap();
+        assert_eq!(numbers(&matches), vec![22.99]);
+    }
+
+    #[test]
+    fn selects_with_slice() {
+        let root = store();
+        let matches = select(&root, "$.store.book[0:1]").unwrap();
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn selects_with_reverse_slice_and_no_explicit_end() {
+        let root = JsonValue::Array(vec![
+            JsonValue::Number(Number::Integer(10)),
+            JsonValue::Number(Number::Integer(20)),
+            JsonValue::Number(Number::Integer(30)),
+        ]);
+        let matches = select(&root, "$[::-1]").unwrap();
+        assert_eq!(numbers(&matches), vec![30.0, 20.0, 10.0]);
+
+        let matches = select(&root, "$[2::-1]").unwrap();
+        assert_eq!(numbers(&matches), vec![30.0, 20.0, 10.0]);
+    }
+
+    #[test]
+    fn errors_on_indexing_a_non_array() {
+        let root = store();
+        assert!(select(&root, "$.store[0]").is_err());
+    }
+
+    #[test]
+    fn errors_on_slicing_a_non_array() {
+        let root = store();
+        assert!(select(&root, "$.store[0:1]").is_err());
+    }
+
+    #[test]
+    fn selects_with_filter_expression() {
+        let root = store();
+        let matches = select(&root, "$.store.book[?(@.price > 10)].category").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert!(matches!(matches[0], JsonValue::String(s) if s == "reference"));
+    }
+
+    #[test]
+    fn errors_on_missing_root() {
+        let root = store();
+        assert!(select(&root, "store.book").is_err());
+    }
+}